@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate lazy_static;
 
-use regex::{Regex, RegexBuilder};
+use regex::{Regex, RegexBuilder, RegexSet, RegexSetBuilder};
 use voca_rs::case;
 
 fn restore_case(origin: &str, to_restore: &str) -> String {
@@ -54,8 +54,8 @@ lazy_static! {
                 (splitted.next().unwrap(), splitted.next().unwrap())
             })
             .collect();
-    static ref PLURAL_RULES: Vec<(Regex, String)> = load_config_map!("../rules/plural.txt");
-    static ref SINGLAR_RULES: Vec<(Regex, String)> = load_config_map!("../rules/singular.txt");
+    static ref PLURAL_RULES: RuleSet = RuleSet::new(load_config_map!("../rules/plural.txt"));
+    static ref SINGLAR_RULES: RuleSet = RuleSet::new(load_config_map!("../rules/singular.txt"));
     static ref UNCOUNTABLE: Vec<Regex> = include_str!("../rules/uncountable.txt")
         .split('\n')
         .filter(|it| it.trim() != "")
@@ -66,6 +66,180 @@ lazy_static! {
         .collect();
 }
 
+/// An owned, runtime-customizable set of inflection rules.
+///
+/// The free functions in this crate (`to_plural`, `to_singular`, ...) operate on a
+/// shared, compile-time rule set. An `Inflector` instead owns its own copy of that
+/// rule set, seeded from the same built-in rules, which can be extended with
+/// [`Inflector::add_plural_rule`], [`Inflector::add_singular_rule`],
+/// [`Inflector::add_irregular`] and [`Inflector::add_uncountable`] to teach it
+/// domain-specific words (e.g. `"vm"` -> `"vms"`).
+///
+/// # Examples
+///
+/// ```
+/// use pluralize_rs::Inflector;
+/// let mut inflector = Inflector::new();
+/// inflector.add_irregular("vm", "vms");
+/// assert_eq!(inflector.to_plural("vm"), "vms");
+/// ```
+pub struct Inflector {
+    irregular: Vec<(String, String)>,
+    plural_rules: RuleSet,
+    singular_rules: RuleSet,
+    uncountable: Vec<Regex>,
+}
+
+impl Inflector {
+    /// Creates a new `Inflector`, seeded with the crate's built-in rules.
+    pub fn new() -> Self {
+        Inflector {
+            irregular: IRREGULAR
+                .iter()
+                .map(|&(singular, plural)| (singular.to_string(), plural.to_string()))
+                .collect(),
+            plural_rules: PLURAL_RULES.clone(),
+            singular_rules: SINGLAR_RULES.clone(),
+            uncountable: UNCOUNTABLE.clone(),
+        }
+    }
+
+    /// Adds a plural rule, taking priority over every rule already registered.
+    pub fn add_plural_rule(&mut self, pattern: &str, replacement: &str) {
+        let rule = RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        self.plural_rules.prepend((rule, replacement.to_string()));
+    }
+
+    /// Adds a singular rule, taking priority over every rule already registered.
+    pub fn add_singular_rule(&mut self, pattern: &str, replacement: &str) {
+        let rule = RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        self.singular_rules.prepend((rule, replacement.to_string()));
+    }
+
+    /// Registers an irregular singular/plural pair (e.g. `"person"`/`"people"`),
+    /// taking priority over every irregular pair already registered.
+    ///
+    /// Every uncountable rule that matches `singular` or `plural` is removed,
+    /// since registering them as irregular implies they are countable. A
+    /// pattern rule (e.g. one for a suffix) is removed as a whole, so the other
+    /// words it covered stop being uncountable too.
+    pub fn add_irregular(&mut self, singular: &str, plural: &str) {
+        let lower_singular = case::lower_case(singular);
+        let lower_plural = case::lower_case(plural);
+        self.uncountable
+            .retain(|r| !r.is_match(&lower_singular) && !r.is_match(&lower_plural));
+        self.irregular.insert(0, (lower_singular, lower_plural));
+    }
+
+    /// Marks `word` as uncountable, taking priority over every rule already registered.
+    pub fn add_uncountable(&mut self, word: &str) {
+        let lower_word = case::lower_case(word);
+        self.irregular
+            .retain(|(singular, plural)| *singular != lower_word && *plural != lower_word);
+        let rule = RegexBuilder::new(&format!("^{}$", regex::escape(word)))
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        self.uncountable.insert(0, rule);
+    }
+
+    /// Returns whether a noun is uncountable. Mirrors [`is_uncountable`].
+    pub fn is_uncountable(&self, word: &str) -> bool {
+        let lower_case = case::lower_case(word);
+        for (singular, plural) in self.irregular.iter() {
+            if lower_case == *singular || lower_case == *plural {
+                return false;
+            }
+        }
+        for r in self.uncountable.iter() {
+            if r.find(&lower_case).is_some() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns a noun's plural form. Mirrors [`to_plural`].
+    pub fn to_plural(&self, word: &str) -> String {
+        if self.is_uncountable(word) {
+            word.to_string()
+        } else {
+            let lower_case = case::lower_case(word);
+            for (singular, plural) in self.irregular.iter() {
+                if lower_case == *singular {
+                    return restore_case(word, plural);
+                }
+            }
+            restore_case(word, &self.plural_rules.apply(word))
+        }
+    }
+
+    /// Returns whether the noun is plural. Mirrors [`is_plural`].
+    pub fn is_plural(&self, word: &str) -> bool {
+        if self.is_uncountable(word) {
+            false
+        } else {
+            let lower_case = case::lower_case(word);
+            for (singular, plural) in self.irregular.iter() {
+                if lower_case == *singular {
+                    return false;
+                } else if lower_case == *plural {
+                    return true;
+                }
+            }
+            lower_case == self.plural_rules.apply(&lower_case)
+        }
+    }
+
+    /// Returns a noun's singular form. Mirrors [`to_singular`].
+    pub fn to_singular(&self, word: &str) -> String {
+        if self.is_uncountable(word) {
+            word.to_string()
+        } else {
+            let lower_case = case::lower_case(word);
+            for (singular, plural) in self.irregular.iter() {
+                if lower_case == *plural {
+                    return restore_case(word, singular);
+                }
+            }
+            restore_case(word, &self.singular_rules.apply(word))
+        }
+    }
+
+    /// Returns whether the noun is singular. Mirrors [`is_singular`].
+    pub fn is_singular(&self, word: &str) -> bool {
+        if self.is_uncountable(word) {
+            false
+        } else {
+            let lower_case = case::lower_case(word);
+            for (singular, plural) in self.irregular.iter() {
+                if lower_case == *plural {
+                    return false;
+                } else if lower_case == *singular {
+                    return true;
+                }
+            }
+            lower_case == self.singular_rules.apply(&lower_case)
+        }
+    }
+}
+
+impl Default for Inflector {
+    fn default() -> Self {
+        Inflector::new()
+    }
+}
+
+lazy_static! {
+    static ref DEFAULT_INFLECTOR: Inflector = Inflector::new();
+}
+
 /// Returns whether a noun is uncountable
 ///
 /// # Arguments
@@ -79,43 +253,63 @@ lazy_static! {
 /// assert!(is_uncountable("water"));
 /// ```
 pub fn is_uncountable(word: &str) -> bool {
-    let lower_case = case::lower_case(word);
-    for (singular, plural) in IRREGULAR.iter() {
-        if lower_case == *singular || lower_case == *plural {
-            return false;
-        }
+    DEFAULT_INFLECTOR.is_uncountable(word)
+}
+
+/// An ordered set of inflection rules, matched in a single pass via a
+/// `RegexSet` before the one rule that actually wins is re-run to extract
+/// capture groups.
+///
+/// Rules are ordered highest-priority first; `prepend` is how new rules (e.g.
+/// from [`Inflector::add_plural_rule`]) take priority over existing ones.
+#[derive(Clone)]
+struct RuleSet {
+    matcher: RegexSet,
+    rules: Vec<(Regex, String)>,
+}
+
+impl RuleSet {
+    fn new(rules: Vec<(Regex, String)>) -> Self {
+        let matcher = Self::build_matcher(&rules);
+        RuleSet { matcher, rules }
+    }
+
+    fn prepend(&mut self, rule: (Regex, String)) {
+        self.rules.insert(0, rule);
+        self.matcher = Self::build_matcher(&self.rules);
+    }
+
+    fn build_matcher(rules: &[(Regex, String)]) -> RegexSet {
+        RegexSetBuilder::new(rules.iter().map(|(re, _)| re.as_str()))
+            .case_insensitive(true)
+            .build()
+            .unwrap()
     }
-    for r in UNCOUNTABLE.iter() {
-        if r.find(&lower_case).is_some() {
-            return true;
+
+    fn apply(&self, word: &str) -> String {
+        match self.matcher.matches(word).iter().next() {
+            Some(index) => replace_with_rule(word, &self.rules[index]),
+            None => word.to_string(),
         }
     }
-    false
 }
 
-fn replace_with_rules(
-    word: &str,
-    mut rules: impl Iterator<Item=&'static (Regex, String)>,
-) -> String {
-    if let Some((m, mut r)) = rules
-        .find_map(|(re, replace_to)| re.captures(&word).map(move |it| (it, replace_to.clone())))
-    {
-        if r == "$0" {
-            return word.to_string();
-        }
-        let mut result = word[0..m.get(0).unwrap().start()].to_string();
-        for (i, content) in ["$1", "$2"].iter().enumerate() {
-            r = if let Some(replace_to) = m.get(i + 1).map(|it| &word[it.start()..it.end()]) {
-                r.replace(content, replace_to)
-            } else {
-                r.replace(content, "")
-            }
+fn replace_with_rule(word: &str, (re, replace_to): &(Regex, String)) -> String {
+    let m = re.captures(word).unwrap();
+    let mut r = replace_to.clone();
+    if r == "$0" {
+        return word.to_string();
+    }
+    let mut result = word[0..m.get(0).unwrap().start()].to_string();
+    for (i, content) in ["$1", "$2"].iter().enumerate() {
+        r = if let Some(replace_to) = m.get(i + 1).map(|it| &word[it.start()..it.end()]) {
+            r.replace(content, replace_to)
+        } else {
+            r.replace(content, "")
         }
-        result.push_str(&r);
-        result
-    } else {
-        word.to_string()
     }
+    result.push_str(&r);
+    result
 }
 
 /// Returns a noun's plural form, if it is uncountable, the origin value will be returned
@@ -131,17 +325,7 @@ fn replace_with_rules(
 /// assert_eq!(to_plural("word"), "words");
 /// ```
 pub fn to_plural(word: &str) -> String {
-    if is_uncountable(word) {
-        word.to_string()
-    } else {
-        let lower_case = case::lower_case(word);
-        for (singular, plural) in IRREGULAR.iter() {
-            if lower_case == *singular {
-                return restore_case(word, plural);
-            }
-        }
-        restore_case(word, &replace_with_rules(&word, PLURAL_RULES.iter()))
-    }
+    DEFAULT_INFLECTOR.to_plural(word)
 }
 
 /// Returns wheter the noun is plural, if it is uncountable, will return true
@@ -158,19 +342,7 @@ pub fn to_plural(word: &str) -> String {
 /// assert!(!is_plural("word"));
 /// ```
 pub fn is_plural(word: &str) -> bool {
-    if is_uncountable(word) {
-        false
-    } else {
-        let lower_case = case::lower_case(word);
-        for (singular, plural) in IRREGULAR.iter() {
-            if lower_case == *singular {
-                return false;
-            } else if lower_case == *plural {
-                return true;
-            }
-        }
-        lower_case == replace_with_rules(&lower_case, PLURAL_RULES.iter())
-    }
+    DEFAULT_INFLECTOR.is_plural(word)
 }
 
 /// Returns a noun's singular form, if it is uncountable, the origin value will be returned
@@ -186,17 +358,7 @@ pub fn is_plural(word: &str) -> bool {
 /// assert_eq!(to_singular("words"), "word");
 /// ```
 pub fn to_singular(word: &str) -> String {
-    if is_uncountable(word) {
-        word.to_string()
-    } else {
-        let lower_case = case::lower_case(word);
-        for (singular, plural) in IRREGULAR.iter() {
-            if lower_case == *plural {
-                return restore_case(word, singular);
-            }
-        }
-        restore_case(word, &replace_with_rules(&word, SINGLAR_RULES.iter()))
-    }
+    DEFAULT_INFLECTOR.to_singular(word)
 }
 
 /// Returns wheter the noun is singular, if it is uncountable, will return true
@@ -213,21 +375,209 @@ pub fn to_singular(word: &str) -> String {
 /// assert!(is_singular("word"));
 /// ```
 pub fn is_singular(word: &str) -> bool {
-    if is_uncountable(word) {
-        false
+    DEFAULT_INFLECTOR.is_singular(word)
+}
+
+/// Returns a noun's plural or singular form depending on `count`, optionally
+/// prefixed with the count itself.
+///
+/// The singular form is used when `count`'s absolute value is `1`, the plural
+/// form otherwise. Case is restored the same way as in [`to_plural`]/[`to_singular`].
+///
+/// # Arguments
+///
+/// * `word` - The noun
+/// * `count` - How many of the noun there are
+/// * `inclusive` - Whether to prefix the result with `count`
+///
+/// # Examples
+///
+/// ```
+/// use pluralize_rs::pluralize;
+/// assert_eq!(pluralize("apple", 3, true), "3 apples");
+/// assert_eq!(pluralize("apple", 1, true), "1 apple");
+/// assert_eq!(pluralize("Apple", 2, false), "Apples");
+/// ```
+pub fn pluralize(word: &str, count: i64, inclusive: bool) -> String {
+    let form = if count.abs() == 1 {
+        to_singular(word)
     } else {
-        let lower_case = case::lower_case(word);
-        for (singular, plural) in IRREGULAR.iter() {
-            if lower_case == *plural {
-                return false;
-            } else if lower_case == *singular {
-                return true;
+        to_plural(word)
+    };
+    if inclusive {
+        format!("{} {}", count, form)
+    } else {
+        form
+    }
+}
+
+/// A CLDR grammatical plural category, as used by ICU message formatting to
+/// pick the right message variant for a number.
+///
+/// Which categories a given locale actually uses depends on its plural rules;
+/// English, for instance, only ever produces [`PluralCategory::One`] or
+/// [`PluralCategory::Other`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+/// The CLDR "operands" derived from a number, used to evaluate plural rules.
+///
+/// * `n` - the absolute value of the number
+/// * `i` - the integer part
+/// * `v` - the number of visible fraction digits, with trailing zeros
+/// * `w` - the number of visible fraction digits, without trailing zeros
+/// * `f` - the visible fraction digits, with trailing zeros, as an integer
+/// * `t` - the visible fraction digits, without trailing zeros, as an integer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PluralOperands {
+    pub n: f64,
+    pub i: u64,
+    pub v: usize,
+    pub w: usize,
+    pub f: u64,
+    pub t: u64,
+}
+
+impl PluralOperands {
+    /// Parses the operands out of a formatted decimal string, preserving
+    /// trailing fraction zeros in `v`/`f`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` isn't a plain decimal number (e.g. scientific notation
+    /// or non-numeric input).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pluralize_rs::PluralOperands;
+    /// let operands = PluralOperands::from_str("1.50");
+    /// assert_eq!(operands.v, 2);
+    /// assert_eq!(operands.w, 1);
+    /// assert_eq!(operands.f, 50);
+    /// assert_eq!(operands.t, 5);
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> PluralOperands {
+        let n: f64 = s.parse::<f64>().unwrap().abs();
+        let mut parts = s.splitn(2, '.');
+        let integer_part = parts.next().unwrap();
+        let i: u64 = integer_part.trim_start_matches('-').parse().unwrap();
+        let fraction_part = parts.next().unwrap_or("");
+        let v = fraction_part.len();
+        let trimmed = fraction_part.trim_end_matches('0');
+        let w = trimmed.len();
+        let f: u64 = if fraction_part.is_empty() {
+            0
+        } else {
+            fraction_part.parse().unwrap()
+        };
+        let t: u64 = if trimmed.is_empty() {
+            0
+        } else {
+            trimmed.parse().unwrap()
+        };
+        PluralOperands { n, i, v, w, f, t }
+    }
+}
+
+impl From<i64> for PluralOperands {
+    fn from(n: i64) -> Self {
+        PluralOperands {
+            n: n.unsigned_abs() as f64,
+            i: n.unsigned_abs(),
+            v: 0,
+            w: 0,
+            f: 0,
+            t: 0,
+        }
+    }
+}
+
+impl From<f64> for PluralOperands {
+    fn from(n: f64) -> Self {
+        if n.is_finite() {
+            PluralOperands::from_str(&n.to_string())
+        } else {
+            PluralOperands {
+                n: n.abs(),
+                i: 0,
+                v: 0,
+                w: 0,
+                f: 0,
+                t: 0,
             }
         }
-        lower_case == replace_with_rules(&lower_case, SINGLAR_RULES.iter())
     }
 }
 
+/// Selects the CLDR plural category for `n`, the way ICU plural rules do.
+///
+/// Only the English ruleset is implemented: [`PluralCategory::One`] when the
+/// integer part is `1` with no fraction digits, [`PluralCategory::Other`]
+/// otherwise. The operand plumbing is kept general so other locales' rules
+/// can be added later.
+///
+/// # Examples
+///
+/// ```
+/// use pluralize_rs::{plural_category, PluralCategory, PluralOperands};
+/// assert_eq!(plural_category(PluralOperands::from(1i64)), PluralCategory::One);
+/// assert_eq!(plural_category(PluralOperands::from(2i64)), PluralCategory::Other);
+/// ```
+pub fn plural_category(n: PluralOperands) -> PluralCategory {
+    if n.i == 1 && n.v == 0 {
+        PluralCategory::One
+    } else {
+        PluralCategory::Other
+    }
+}
+
+/// Returns the ordinal suffix for `n` (`"st"`, `"nd"`, `"rd"` or `"th"`).
+///
+/// # Examples
+///
+/// ```
+/// use pluralize_rs::ordinal;
+/// assert_eq!(ordinal(1), "st");
+/// assert_eq!(ordinal(11), "th");
+/// assert_eq!(ordinal(22), "nd");
+/// ```
+pub fn ordinal(n: i64) -> &'static str {
+    let n = n.unsigned_abs();
+    if n % 100 == 11 || n % 100 == 12 || n % 100 == 13 {
+        "th"
+    } else {
+        match n % 10 {
+            1 => "st",
+            2 => "nd",
+            3 => "rd",
+            _ => "th",
+        }
+    }
+}
+
+/// Returns `n` followed by its ordinal suffix, e.g. `ordinalize(22)` -> `"22nd"`.
+///
+/// # Examples
+///
+/// ```
+/// use pluralize_rs::ordinalize;
+/// assert_eq!(ordinalize(1), "1st");
+/// assert_eq!(ordinalize(22), "22nd");
+/// assert_eq!(ordinalize(113), "113th");
+/// ```
+pub fn ordinalize(n: i64) -> String {
+    format!("{}{}", n, ordinal(n))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,4 +605,10 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn matches_rules_case_insensitively() {
+        assert_eq!(to_plural("BOX"), "BOXES");
+        assert_eq!(to_plural("MATRIX"), "MATRICES");
+    }
 }